@@ -1,6 +1,10 @@
 use std::fmt::Write as _;
-use std::{env, io};
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::{env, fs, io};
 
+use comrak::nodes::{AstNode, NodeCodeBlock, NodeValue};
+use comrak::{Arena, ComrakOptions, format_html, parse_document};
 use mdbook_preprocessor::book::{Book, BookItem};
 use mdbook_preprocessor::errors::Result;
 use mdbook_preprocessor::{MDBOOK_VERSION, Preprocessor, PreprocessorContext, parse_input};
@@ -12,9 +16,17 @@ struct LanguageTabsPreprocessor;
 struct ParsedTabItem {
     tab_label: String,
     tab_value: String,
+    is_default_tab: bool,
+    rendered_inner_html: String,
+    single_code_block: Option<SingleCodeBlock>,
+}
+
+struct SingleCodeBlock {
     code_language: String,
     code_content: String,
-    is_default_tab: bool,
+    title: Option<String>,
+    show_line_numbers: bool,
+    highlighted_line_ranges: Vec<RangeInclusive<usize>>,
 }
 
 impl Preprocessor for LanguageTabsPreprocessor {
@@ -22,9 +34,11 @@ impl Preprocessor for LanguageTabsPreprocessor {
         "language-tabs"
     }
 
-    fn run(&self, _context: &PreprocessorContext, book: Book) -> Result<Book> {
+    fn run(&self, context: &PreprocessorContext, book: Book) -> Result<Book> {
+        let highlighting_enabled = is_highlighting_enabled(context, self.name());
+        let book_src_root = context.root.join(&context.config.book.src);
         let mut transformed_book = book;
-        transform_book_items(&mut transformed_book.items);
+        transform_book_items(&mut transformed_book.items, highlighting_enabled, &book_src_root);
         Ok(transformed_book)
     }
 
@@ -33,103 +47,697 @@ impl Preprocessor for LanguageTabsPreprocessor {
     }
 }
 
-fn transform_book_items(book_items: &mut [BookItem]) {
+fn is_highlighting_enabled(context: &PreprocessorContext, preprocessor_name: &str) -> bool {
+    context
+        .config
+        .get::<bool>(&format!("preprocessor.{preprocessor_name}.highlight"))
+        .ok()
+        .flatten()
+        .unwrap_or(false)
+}
+
+fn transform_book_items(book_items: &mut [BookItem], highlighting_enabled: bool, book_src_root: &Path) {
     for book_item in book_items {
         match book_item {
             BookItem::Chapter(chapter) => {
-                chapter.content = transform_docusaurus_tabs_blocks(&chapter.content);
-                transform_book_items(&mut chapter.sub_items);
+                let content_with_includes_expanded =
+                    expand_include_directives_in_tab_items(&chapter.content, book_src_root);
+                chapter.content = transform_docusaurus_tabs_blocks(
+                    &content_with_includes_expanded,
+                    highlighting_enabled,
+                );
+                transform_book_items(&mut chapter.sub_items, highlighting_enabled, book_src_root);
             }
             BookItem::Separator | BookItem::PartTitle(_) => {}
         }
     }
 }
 
-fn transform_docusaurus_tabs_blocks(markdown_content: &str) -> String {
-    let mut transformed_markdown = String::with_capacity(markdown_content.len());
-    let mut search_start_index = 0usize;
-    let tabs_open_marker = "<Tabs";
-    let tabs_close_marker = "</Tabs>";
+/// Expands `{{#include}}`/`{{#rustdoc_include}}` directives found inside `<TabItem>`
+/// bodies before the comrak pass runs, the way mdbook's own `links.rs` expands them
+/// for the rest of a chapter, so tab code samples can stay in sync with real sources.
+///
+/// Walks the same comrak AST and `<TabItem>`/`</TabItem>` depth tracking that
+/// `transform_docusaurus_tabs_blocks` uses, rather than a flat `.find()` scan that
+/// pairs the first open tag with the first subsequent close tag: a nested `<Tabs>`
+/// group closes its own `</TabItem>` first, and a flat scan would mistake that for
+/// the outer item's close, leaving any `{{#include ...}}` after the nested group
+/// unexpanded.
+///
+/// Tag lines are classified one physical line at a time via
+/// `split_html_block_into_tag_lines`, the same way `transform_docusaurus_tabs_blocks`
+/// does, since CommonMark's HTML-block rule 7 merges a `<TabItem>` open immediately
+/// following a `<Tabs>` open (or the next `<TabItem>` immediately following a
+/// `</TabItem>`) with no blank line between them into a single `HtmlBlock` node —
+/// classifying only the node's first line would silently miss every tag after it.
+fn expand_include_directives_in_tab_items(markdown_content: &str, book_src_root: &Path) -> String {
+    let arena = Arena::new();
+    let root_node = parse_document(&arena, markdown_content, &comrak_parse_options());
+    let source_line_index = SourceLineIndex::new(markdown_content);
 
-    while let Some(relative_tabs_start_index) =
-        markdown_content[search_start_index..].find(tabs_open_marker)
-    {
-        let tabs_start_index = search_start_index + relative_tabs_start_index;
-        transformed_markdown.push_str(&markdown_content[search_start_index..tabs_start_index]);
+    let mut expanded_markdown = String::with_capacity(markdown_content.len());
+    let mut cursor_byte_offset = 0usize;
+    let mut open_tab_item_depth = 0usize;
 
-        let Some(open_tag_end_relative_index) = markdown_content[tabs_start_index..].find('>')
-        else {
-            transformed_markdown.push_str(&markdown_content[tabs_start_index..]);
-            return transformed_markdown;
-        };
-        let tabs_open_tag_end_index = tabs_start_index + open_tag_end_relative_index;
+    for top_level_node in root_node.children() {
+        let sourcepos = top_level_node.data.borrow().sourcepos;
+        let node_start_byte =
+            source_line_index.byte_offset(sourcepos.start.line, sourcepos.start.column);
+        let node_end_byte =
+            source_line_index.byte_offset(sourcepos.end.line, sourcepos.end.column + 1);
 
-        let Some(close_tag_start_relative_index) =
-            markdown_content[tabs_open_tag_end_index + 1..].find(tabs_close_marker)
+        let Some(tag_lines) =
+            split_html_block_into_tag_lines(top_level_node, &source_line_index, node_end_byte)
         else {
-            transformed_markdown.push_str(&markdown_content[tabs_start_index..]);
-            return transformed_markdown;
+            if open_tab_item_depth > 0 {
+                expanded_markdown.push_str(&markdown_content[cursor_byte_offset..node_start_byte]);
+                let node_text = &markdown_content[node_start_byte..node_end_byte];
+                expanded_markdown.push_str(&expand_include_directives(node_text, book_src_root));
+                cursor_byte_offset = node_end_byte;
+            }
+            continue;
         };
-        let tabs_close_tag_start_index =
-            tabs_open_tag_end_index + 1 + close_tag_start_relative_index;
 
-        let tabs_open_tag = &markdown_content[tabs_start_index..=tabs_open_tag_end_index];
-        let tabs_inner_content =
-            &markdown_content[tabs_open_tag_end_index + 1..tabs_close_tag_start_index];
+        for tag_line in tag_lines {
+            match tag_line.tag {
+                Some(TabsComponentTag::TabItemOpen { .. }) => {
+                    open_tab_item_depth += 1;
+                }
+                Some(TabsComponentTag::TabItemClose) => {
+                    open_tab_item_depth = open_tab_item_depth.saturating_sub(1);
+                }
+                _ if open_tab_item_depth > 0 => {
+                    expanded_markdown
+                        .push_str(&markdown_content[cursor_byte_offset..tag_line.line_start_byte]);
+                    let line_text =
+                        &markdown_content[tag_line.line_start_byte..tag_line.line_end_byte];
+                    expanded_markdown.push_str(&expand_include_directives(line_text, book_src_root));
+                    cursor_byte_offset = tag_line.line_end_byte;
+                }
+                _ => {}
+            }
+        }
+    }
 
-        if let Some(rendered_tabs_html) = render_tabs_group_html(tabs_open_tag, tabs_inner_content)
-        {
-            transformed_markdown.push('\n');
-            transformed_markdown.push_str(rendered_tabs_html.trim());
-            transformed_markdown.push_str("\n\n");
+    expanded_markdown.push_str(&markdown_content[cursor_byte_offset..]);
+    expanded_markdown
+}
+
+fn expand_include_directives(markdown_content: &str, book_src_root: &Path) -> String {
+    let include_directive_regex = Regex::new(
+        r"\{\{\s*#(include|rustdoc_include)\s+([^\s:}]+)(?::([^}\s]+))?\s*\}\}",
+    )
+    .expect("static include directive pattern is valid");
+
+    include_directive_regex
+        .replace_all(markdown_content, |captures: &regex::Captures| {
+            let directive_name = &captures[1];
+            let included_file_path = &captures[2];
+            let range_specifier = captures.get(3).map(|range_match| range_match.as_str());
+            resolve_include_directive(
+                book_src_root,
+                directive_name,
+                included_file_path,
+                range_specifier,
+            )
+            .unwrap_or_else(|| captures[0].to_string())
+        })
+        .into_owned()
+}
+
+fn resolve_include_directive(
+    book_src_root: &Path,
+    directive_name: &str,
+    included_file_path: &str,
+    range_specifier: Option<&str>,
+) -> Option<String> {
+    let included_file_contents = fs::read_to_string(book_src_root.join(included_file_path)).ok()?;
+
+    let selected_contents = match range_specifier {
+        Some(range_specifier) => select_include_range(&included_file_contents, range_specifier),
+        None => included_file_contents,
+    };
+
+    let selected_contents = if directive_name == "rustdoc_include" {
+        strip_rustdoc_hidden_lines(&selected_contents)
+    } else {
+        selected_contents
+    };
+
+    Some(selected_contents.trim_end_matches('\n').to_string())
+}
+
+fn select_include_range(file_contents: &str, range_specifier: &str) -> String {
+    match parse_include_line_range(range_specifier) {
+        Some(line_range) => file_contents
+            .lines()
+            .enumerate()
+            .filter(|(line_index, _)| line_range.contains(&(line_index + 1)))
+            .map(|(_, line)| line)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        None => select_include_anchor_range(file_contents, range_specifier),
+    }
+}
+
+fn parse_include_line_range(range_specifier: &str) -> Option<std::ops::Range<usize>> {
+    if let Some((start_line_text, end_line_text)) = range_specifier.split_once(':') {
+        let start_line: usize = start_line_text.parse().ok()?;
+        let end_line = if end_line_text.is_empty() {
+            usize::MAX
         } else {
-            transformed_markdown.push_str(
-                &markdown_content
-                    [tabs_start_index..tabs_close_tag_start_index + tabs_close_marker.len()],
-            );
+            end_line_text.parse().ok()?
+        };
+        Some(start_line..end_line.saturating_add(1))
+    } else {
+        let only_line: usize = range_specifier.parse().ok()?;
+        Some(only_line..only_line + 1)
+    }
+}
+
+fn select_include_anchor_range(file_contents: &str, anchor_name: &str) -> String {
+    let anchor_start_marker = format!("ANCHOR: {anchor_name}");
+    let anchor_end_marker = format!("ANCHOR_END: {anchor_name}");
+
+    let mut is_inside_anchor = false;
+    let mut anchor_lines: Vec<&str> = Vec::new();
+
+    for line in file_contents.lines() {
+        if line.contains(&anchor_end_marker) {
+            is_inside_anchor = false;
+            continue;
+        }
+        if is_inside_anchor {
+            if !line.contains("ANCHOR") {
+                anchor_lines.push(line);
+            }
+            continue;
+        }
+        if line.contains(&anchor_start_marker) {
+            is_inside_anchor = true;
+        }
+    }
+
+    anchor_lines.join("\n")
+}
+
+/// Applies rustdoc's hidden-line convention: `# `-prefixed lines (and bare `#`
+/// lines) are dropped, while a `##`-prefixed line is unescaped to a literal `#`.
+fn strip_rustdoc_hidden_lines(snippet: &str) -> String {
+    snippet
+        .lines()
+        .filter_map(|line| {
+            let trimmed_line = line.trim_start();
+            if trimmed_line == "#" || trimmed_line.starts_with("# ") {
+                None
+            } else if let Some(escaped_line) = line.strip_prefix("##") {
+                Some(format!("#{escaped_line}"))
+            } else {
+                Some(line.to_string())
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+enum TabsComponentTag {
+    TabsOpen { raw_group_identifier: Option<String> },
+    TabsClose,
+    TabItemOpen { tab_item_attributes: String },
+    TabItemClose,
+}
+
+struct OpenTabsGroupContext<'a> {
+    explicit_raw_group_identifier: Option<String>,
+    tabs_open_start_byte: usize,
+    parsed_tab_items: Vec<ParsedTabItem>,
+    any_tab_item_failed: bool,
+    pending_tab_item: Option<PendingTabItem<'a>>,
+}
+
+struct PendingTabItem<'a> {
+    tab_label: String,
+    tab_value: String,
+    is_default_tab: bool,
+    body_pieces: Vec<TabItemBodyPiece<'a>>,
+}
+
+enum TabItemBodyPiece<'a> {
+    Node(&'a AstNode<'a>),
+    RenderedHtml(String),
+}
+
+/// Maps comrak's 1-based (line, column) source positions back to byte offsets
+/// in the original chapter text, so unrecognised content can be copied through verbatim.
+struct SourceLineIndex {
+    line_start_byte_offsets: Vec<usize>,
+}
+
+impl SourceLineIndex {
+    fn new(markdown_content: &str) -> Self {
+        let mut line_start_byte_offsets = vec![0usize];
+        for (byte_index, byte) in markdown_content.bytes().enumerate() {
+            if byte == b'\n' {
+                line_start_byte_offsets.push(byte_index + 1);
+            }
         }
+        Self {
+            line_start_byte_offsets,
+        }
+    }
+
+    fn byte_offset(&self, line: usize, column: usize) -> usize {
+        let line_start_byte_offset = self
+            .line_start_byte_offsets
+            .get(line.saturating_sub(1))
+            .copied()
+            .unwrap_or(0);
+        line_start_byte_offset + column.saturating_sub(1)
+    }
+}
+
+fn comrak_parse_options<'a>() -> ComrakOptions<'a> {
+    ComrakOptions::default()
+}
+
+fn comrak_render_options<'a>() -> ComrakOptions<'a> {
+    let mut render_options = ComrakOptions::default();
+    render_options.render.unsafe_ = true;
+    render_options
+}
+
+fn parse_tabs_component_tag(trimmed_html: &str) -> Option<TabsComponentTag> {
+    if trimmed_html.starts_with("</Tabs>") {
+        Some(TabsComponentTag::TabsClose)
+    } else if trimmed_html.starts_with("</TabItem>") {
+        Some(TabsComponentTag::TabItemClose)
+    } else if let Some(after_tag_name) = trimmed_html.strip_prefix("<Tabs") {
+        let tag_end_index = after_tag_name.find('>')?;
+        let tab_attributes = &after_tag_name[..tag_end_index];
+        Some(TabsComponentTag::TabsOpen {
+            raw_group_identifier: parse_attribute_value(tab_attributes, "groupId"),
+        })
+    } else if let Some(after_tag_name) = trimmed_html.strip_prefix("<TabItem") {
+        let tag_end_index = after_tag_name.find('>')?;
+        let tab_item_attributes = after_tag_name[..tag_end_index]
+            .trim_end_matches('/')
+            .to_string();
+        Some(TabsComponentTag::TabItemOpen { tab_item_attributes })
+    } else {
+        None
+    }
+}
+
+struct HtmlBlockTagLine {
+    tag: Option<TabsComponentTag>,
+    line_start_byte: usize,
+    line_end_byte: usize,
+}
+
+/// Splits an `HtmlBlock` node's literal into its physical source lines and classifies
+/// each one independently, instead of treating the whole (possibly multi-line) literal
+/// as a single tag. CommonMark's HTML-block rule 7 merges any run of non-blank lines
+/// following a bare tag line into one `HtmlBlock` node — exactly the canonical
+/// Docusaurus layout where `<TabItem>` immediately follows `<Tabs>` (or the next
+/// `<TabItem>` immediately follows a `</TabItem>`) with no blank line between them —
+/// so a single `parse_tabs_component_tag` call over the merged literal would see only
+/// its first line and silently discard every tag after it. Returns `None` if the node
+/// isn't an `HtmlBlock` at all.
+fn split_html_block_into_tag_lines<'a>(
+    node: &'a AstNode<'a>,
+    source_line_index: &SourceLineIndex,
+    node_end_byte: usize,
+) -> Option<Vec<HtmlBlockTagLine>> {
+    let node_data = node.data.borrow();
+    let (html_literal, first_line_number) = match &node_data.value {
+        NodeValue::HtmlBlock(node_html_block) => {
+            (node_html_block.literal.as_str(), node_data.sourcepos.start.line)
+        }
+        _ => return None,
+    };
+
+    let physical_lines: Vec<&str> = html_literal.lines().collect();
+    let last_relative_line_index = physical_lines.len().saturating_sub(1);
+
+    Some(
+        physical_lines
+            .into_iter()
+            .enumerate()
+            .map(|(relative_line_index, line_text)| {
+                let absolute_line_number = first_line_number + relative_line_index;
+                let line_start_byte = source_line_index.byte_offset(absolute_line_number, 1);
+                let line_end_byte = if relative_line_index == last_relative_line_index {
+                    node_end_byte
+                } else {
+                    source_line_index.byte_offset(absolute_line_number + 1, 1)
+                };
+                HtmlBlockTagLine {
+                    tag: parse_tabs_component_tag(line_text.trim()),
+                    line_start_byte,
+                    line_end_byte,
+                }
+            })
+            .collect(),
+    )
+}
+
+fn transform_docusaurus_tabs_blocks(markdown_content: &str, highlighting_enabled: bool) -> String {
+    let arena = Arena::new();
+    let root_node = parse_document(&arena, markdown_content, &comrak_parse_options());
+    let source_line_index = SourceLineIndex::new(markdown_content);
+
+    let mut transformed_markdown = String::with_capacity(markdown_content.len());
+    let mut cursor_byte_offset = 0usize;
+    let mut open_groups: Vec<OpenTabsGroupContext> = Vec::new();
+
+    for top_level_node in root_node.children() {
+        let sourcepos = top_level_node.data.borrow().sourcepos;
+        let node_end_byte =
+            source_line_index.byte_offset(sourcepos.end.line, sourcepos.end.column + 1);
+
+        let Some(tag_lines) =
+            split_html_block_into_tag_lines(top_level_node, &source_line_index, node_end_byte)
+        else {
+            if let Some(open_group) = open_groups.last_mut()
+                && let Some(pending_tab_item) = open_group.pending_tab_item.as_mut()
+            {
+                pending_tab_item
+                    .body_pieces
+                    .push(TabItemBodyPiece::Node(top_level_node));
+            }
+            continue;
+        };
+
+        for tag_line in tag_lines {
+            match tag_line.tag {
+                Some(TabsComponentTag::TabsOpen {
+                    raw_group_identifier,
+                }) => {
+                    open_groups.push(OpenTabsGroupContext {
+                        explicit_raw_group_identifier: raw_group_identifier,
+                        tabs_open_start_byte: tag_line.line_start_byte,
+                        parsed_tab_items: Vec::new(),
+                        any_tab_item_failed: false,
+                        pending_tab_item: None,
+                    });
+                }
+                Some(TabsComponentTag::TabItemOpen {
+                    tab_item_attributes,
+                }) => {
+                    if let Some(open_group) = open_groups.last_mut() {
+                        let tab_label = parse_attribute_value(&tab_item_attributes, "label")
+                            .or_else(|| parse_attribute_value(&tab_item_attributes, "value"))
+                            .unwrap_or_else(|| {
+                                format!("Tab {}", open_group.parsed_tab_items.len() + 1)
+                            });
+                        let tab_value = parse_attribute_value(&tab_item_attributes, "value")
+                            .unwrap_or_else(|| sanitize_identifier(&tab_label));
+                        let is_default_tab = tab_item_attributes.contains("default");
+
+                        open_group.pending_tab_item = Some(PendingTabItem {
+                            tab_label,
+                            tab_value,
+                            is_default_tab,
+                            body_pieces: Vec::new(),
+                        });
+                    }
+                }
+                Some(TabsComponentTag::TabItemClose) => {
+                    if let Some(open_group) = open_groups.last_mut()
+                        && let Some(pending_tab_item) = open_group.pending_tab_item.take()
+                    {
+                        match finalize_tab_item(pending_tab_item) {
+                            Some(parsed_tab_item) => open_group.parsed_tab_items.push(parsed_tab_item),
+                            None => open_group.any_tab_item_failed = true,
+                        }
+                    }
+                }
+                Some(TabsComponentTag::TabsClose) => {
+                    let Some(closed_group) = open_groups.pop() else {
+                        continue;
+                    };
+                    let rendered_tabs_html = if closed_group.any_tab_item_failed {
+                        None
+                    } else {
+                        render_tabs_group_html(
+                            closed_group.explicit_raw_group_identifier,
+                            closed_group.parsed_tab_items,
+                            highlighting_enabled,
+                        )
+                    };
 
-        search_start_index = tabs_close_tag_start_index + tabs_close_marker.len();
+                    if let Some(parent_group) = open_groups.last_mut() {
+                        if let Some(pending_tab_item) = parent_group.pending_tab_item.as_mut() {
+                            match rendered_tabs_html {
+                                Some(html) => pending_tab_item
+                                    .body_pieces
+                                    .push(TabItemBodyPiece::RenderedHtml(html)),
+                                None => parent_group.any_tab_item_failed = true,
+                            }
+                        }
+                    } else {
+                        transformed_markdown.push_str(
+                            &markdown_content
+                                [cursor_byte_offset..closed_group.tabs_open_start_byte],
+                        );
+                        match rendered_tabs_html {
+                            Some(html) => {
+                                transformed_markdown.push('\n');
+                                transformed_markdown.push_str(html.trim());
+                                transformed_markdown.push_str("\n\n");
+                            }
+                            None => transformed_markdown.push_str(
+                                &markdown_content
+                                    [closed_group.tabs_open_start_byte..tag_line.line_end_byte],
+                            ),
+                        }
+                        cursor_byte_offset = tag_line.line_end_byte;
+                    }
+                }
+                None => {
+                    // A non-tag line merged into the same `HtmlBlock` (e.g. free text
+                    // directly following a tag line with no blank line separating
+                    // them) has no AST subtree of its own to render; keep it as
+                    // escaped literal text instead of silently dropping it.
+                    if let Some(open_group) = open_groups.last_mut()
+                        && let Some(pending_tab_item) = open_group.pending_tab_item.as_mut()
+                    {
+                        let line_text =
+                            &markdown_content[tag_line.line_start_byte..tag_line.line_end_byte];
+                        pending_tab_item.body_pieces.push(TabItemBodyPiece::RenderedHtml(
+                            escape_html_text_content(line_text),
+                        ));
+                    }
+                }
+            }
+        }
     }
 
-    transformed_markdown.push_str(&markdown_content[search_start_index..]);
+    transformed_markdown.push_str(&markdown_content[cursor_byte_offset..]);
     transformed_markdown
 }
 
-fn render_tabs_group_html(tabs_open_tag: &str, tabs_inner_content: &str) -> Option<String> {
-    let group_identifier_regex = Regex::new(r#"groupId\s*=\s*"([^"]+)""#).ok()?;
-    let raw_group_identifier = group_identifier_regex
-        .captures(tabs_open_tag)
-        .and_then(|captures| captures.get(1).map(|value| value.as_str().to_string()))
-        .unwrap_or_else(|| "language-tabs-group".to_string());
+fn finalize_tab_item(pending_tab_item: PendingTabItem<'_>) -> Option<ParsedTabItem> {
+    if pending_tab_item.body_pieces.is_empty() {
+        return None;
+    }
+
+    let single_code_block = match pending_tab_item.body_pieces.as_slice() {
+        [TabItemBodyPiece::Node(node)] => match &node.data.borrow().value {
+            NodeValue::CodeBlock(node_code_block) => {
+                Some(single_code_block_from(node_code_block))
+            }
+            _ => None,
+        },
+        _ => None,
+    };
+
+    Some(ParsedTabItem {
+        tab_label: pending_tab_item.tab_label,
+        tab_value: pending_tab_item.tab_value,
+        is_default_tab: pending_tab_item.is_default_tab,
+        rendered_inner_html: render_tab_item_body_to_html(&pending_tab_item.body_pieces),
+        single_code_block,
+    })
+}
+
+fn single_code_block_from(node_code_block: &NodeCodeBlock) -> SingleCodeBlock {
+    let parsed_metastring = parse_code_fence_metastring(&node_code_block.info);
+    let code_content = node_code_block
+        .literal
+        .trim_end_matches('\n')
+        .to_string();
+
+    SingleCodeBlock {
+        code_language: parsed_metastring.code_language,
+        code_content,
+        title: parsed_metastring.title,
+        show_line_numbers: parsed_metastring.show_line_numbers,
+        highlighted_line_ranges: parsed_metastring.highlighted_line_ranges,
+    }
+}
+
+struct ParsedCodeFenceMetastring {
+    code_language: String,
+    title: Option<String>,
+    show_line_numbers: bool,
+    highlighted_line_ranges: Vec<RangeInclusive<usize>>,
+}
+
+/// Parses a Docusaurus-style fence info string, e.g. `rust title="main.rs" {1,4-6}
+/// showLineNumbers`, into the language token plus the annotations that follow it.
+fn parse_code_fence_metastring(info_string: &str) -> ParsedCodeFenceMetastring {
+    let trimmed_info_string = info_string.trim();
+    let (raw_code_language, metastring) = trimmed_info_string
+        .split_once(char::is_whitespace)
+        .unwrap_or((trimmed_info_string, ""));
+
+    let code_language = if raw_code_language.is_empty() {
+        "text".to_string()
+    } else {
+        raw_code_language.to_string()
+    };
+    let title = parse_attribute_value(metastring, "title");
+    let show_line_numbers = metastring.contains("showLineNumbers");
+    let highlighted_line_ranges = metastring
+        .find('{')
+        .zip(metastring.find('}'))
+        .map(|(open_brace_index, close_brace_index)| {
+            parse_highlighted_line_ranges(&metastring[open_brace_index + 1..close_brace_index])
+        })
+        .unwrap_or_default();
+
+    ParsedCodeFenceMetastring {
+        code_language,
+        title,
+        show_line_numbers,
+        highlighted_line_ranges,
+    }
+}
+
+fn parse_highlighted_line_ranges(ranges_text: &str) -> Vec<RangeInclusive<usize>> {
+    ranges_text
+        .split(',')
+        .filter_map(|range_token| {
+            let range_token = range_token.trim();
+            if let Some((start_text, end_text)) = range_token.split_once('-') {
+                let start_line: usize = start_text.trim().parse().ok()?;
+                let end_line: usize = end_text.trim().parse().ok()?;
+                Some(start_line..=end_line)
+            } else {
+                let line_number: usize = range_token.parse().ok()?;
+                Some(line_number..=line_number)
+            }
+        })
+        .collect()
+}
 
-    let tab_item_regex =
-        Regex::new(r"(?s)<TabItem(?P<attributes>[^>]*)>(?P<content>.*?)</TabItem>").ok()?;
-    let mut parsed_tab_items: Vec<ParsedTabItem> = Vec::new();
+/// Renders a single code block's lines as individually wrapped `<span>`s so that
+/// highlighted-line ranges can be marked per line and a blank source line never
+/// becomes a blank HTML line (which would prematurely close the surrounding
+/// CommonMark HTML block).
+fn render_single_code_block_lines_html(
+    single_code_block: &SingleCodeBlock,
+    highlighting_enabled: bool,
+) -> String {
+    let code_lines: Vec<&str> = single_code_block.code_content.split('\n').collect();
+    let total_line_count = code_lines.len();
 
-    for tab_item_capture in tab_item_regex.captures_iter(tabs_inner_content) {
-        let tab_item_attributes = tab_item_capture.name("attributes")?.as_str();
-        let tab_item_content = tab_item_capture.name("content")?.as_str();
+    // Tokenize the whole block in one pass so the lexer sees multi-line tokens (a
+    // block comment, a string continued via `\`) in full; re-lexing each physical
+    // line in isolation misclassifies every line after the token's first one.
+    // `highlight_code_to_html_lines` re-opens a token's span class on every physical
+    // line it crosses, so the result already has exactly one HTML fragment per
+    // physical source line with its class preserved throughout.
+    let highlighted_lines: Option<Vec<String>> = highlighting_enabled
+        .then(|| {
+            highlight_code_to_html_lines(
+                &single_code_block.code_language,
+                &single_code_block.code_content,
+            )
+        })
+        .flatten();
 
-        let tab_label = parse_attribute_value(tab_item_attributes, "label")
-            .or_else(|| parse_attribute_value(tab_item_attributes, "value"))
-            .unwrap_or_else(|| format!("Tab {}", parsed_tab_items.len() + 1));
+    let mut lines_html = String::new();
 
-        let tab_value = parse_attribute_value(tab_item_attributes, "value")
-            .unwrap_or_else(|| sanitize_identifier(&tab_label));
+    for (line_index, line_text) in code_lines.into_iter().enumerate() {
+        let one_based_line_number = line_index + 1;
+        let is_highlighted_line = single_code_block
+            .highlighted_line_ranges
+            .iter()
+            .any(|line_range| {
+                let clamped_start = (*line_range.start()).min(total_line_count);
+                let clamped_end = (*line_range.end()).min(total_line_count);
+                (clamped_start..=clamped_end).contains(&one_based_line_number)
+            });
 
-        let is_default_tab = tab_item_attributes.contains("default");
-        let (code_language, code_content) = extract_first_fenced_code_block(tab_item_content)?;
+        let line_html = highlighted_lines
+            .as_ref()
+            .and_then(|lines| lines.get(line_index))
+            .cloned()
+            .unwrap_or_else(|| escape_html_text_content(line_text));
 
-        parsed_tab_items.push(ParsedTabItem {
-            tab_label,
-            tab_value,
-            code_language,
-            code_content,
-            is_default_tab,
-        });
+        let _ = writeln!(
+            lines_html,
+            r#"<span class="code-line{}">{}</span>"#,
+            if is_highlighted_line {
+                " highlighted-line"
+            } else {
+                ""
+            },
+            line_html,
+        );
     }
 
+    lines_html
+}
+
+/// Recursively renders a `TabItem`'s body nodes (and any already-rendered nested
+/// tab groups) to HTML, the way this code does for the enclosing document, so a
+/// tab can hold prose, lists, and more than one code block instead of just a fence.
+fn render_tab_item_body_to_html(body_pieces: &[TabItemBodyPiece]) -> String {
+    let render_options = comrak_render_options();
+    let mut rendered_html = String::new();
+
+    for body_piece in body_pieces {
+        match body_piece {
+            TabItemBodyPiece::Node(node) => {
+                let mut node_html_bytes = Vec::new();
+                if format_html(node, &render_options, &mut node_html_bytes).is_ok()
+                    && let Ok(node_html) = String::from_utf8(node_html_bytes)
+                {
+                    rendered_html
+                        .push_str(&neutralize_blank_lines_for_html_block_embedding(&node_html));
+                    rendered_html.push('\n');
+                }
+            }
+            TabItemBodyPiece::RenderedHtml(nested_group_html) => {
+                rendered_html.push_str(nested_group_html);
+                rendered_html.push('\n');
+            }
+        }
+    }
+
+    rendered_html
+}
+
+/// The rendered HTML is re-embedded as a CommonMark HTML block, which ends at the
+/// first blank line; a blank line is replaced with an empty-comment placeholder
+/// rather than dropped, so a blank line that is literal content of an embedded
+/// `<pre><code>` block (e.g. between two statements of a code sample) survives intact.
+fn neutralize_blank_lines_for_html_block_embedding(html_content: &str) -> String {
+    html_content
+        .lines()
+        .map(|line| if line.trim().is_empty() { "<!-- -->" } else { line })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_tabs_group_html(
+    explicit_raw_group_identifier: Option<String>,
+    parsed_tab_items: Vec<ParsedTabItem>,
+    highlighting_enabled: bool,
+) -> Option<String> {
     if parsed_tab_items.is_empty() {
         return None;
     }
@@ -139,9 +747,17 @@ fn render_tabs_group_html(tabs_open_tag: &str, tabs_inner_content: &str) -> Opti
         .position(|parsed_tab_item| parsed_tab_item.is_default_tab)
         .unwrap_or(0);
 
-    let sanitized_group_identifier = sanitize_identifier(&raw_group_identifier);
+    let sanitized_group_identifier = sanitize_identifier(
+        explicit_raw_group_identifier
+            .as_deref()
+            .unwrap_or("language-tabs-group"),
+    );
     let mut rendered_tabs_html = String::new();
-    write_tabs_group_start(&mut rendered_tabs_html, &sanitized_group_identifier);
+    write_tabs_group_start(
+        &mut rendered_tabs_html,
+        &sanitized_group_identifier,
+        explicit_raw_group_identifier.as_deref(),
+    );
     write_tabs_group_buttons(
         &mut rendered_tabs_html,
         &parsed_tab_items,
@@ -153,17 +769,38 @@ fn render_tabs_group_html(tabs_open_tag: &str, tabs_inner_content: &str) -> Opti
         &parsed_tab_items,
         active_tab_index,
         &sanitized_group_identifier,
+        highlighting_enabled,
     );
     write_tabs_group_end(&mut rendered_tabs_html);
 
     Some(rendered_tabs_html)
 }
 
-fn write_tabs_group_start(rendered_tabs_html: &mut String, sanitized_group_identifier: &str) {
+/// `sanitized_group_identifier` builds this instance's element ids. It is derived
+/// only from the author's `groupId` (or the fixed `language-tabs-group` fallback),
+/// so two groups on one page with the same `groupId`, or both without one, get
+/// identical element ids. `explicit_raw_group_identifier`, when the author wrote
+/// a `groupId`, is emitted separately as the stable cross-page key a companion
+/// script syncs and persists selection by.
+fn write_tabs_group_start(
+    rendered_tabs_html: &mut String,
+    sanitized_group_identifier: &str,
+    explicit_raw_group_identifier: Option<&str>,
+) {
+    let sync_attribute = explicit_raw_group_identifier
+        .map(|raw_group_identifier| {
+            format!(
+                r#" data-language-tabs-sync="{}""#,
+                escape_html_attribute_value(raw_group_identifier),
+            )
+        })
+        .unwrap_or_default();
+
     let _ = writeln!(
         rendered_tabs_html,
-        r#"<div class="language-tabs" data-language-tabs-group="{}">"#,
+        r#"<div class="language-tabs" data-language-tabs-group="{}"{}>"#,
         escape_html_attribute_value(sanitized_group_identifier),
+        sync_attribute,
     );
     let _ = writeln!(
         rendered_tabs_html,
@@ -197,7 +834,7 @@ fn write_tabs_group_buttons(
             escape_html_attribute_value(&tab_button_identifier),
             escape_html_attribute_value(&tab_panel_identifier),
             if is_active_tab { "true" } else { "false" },
-            escape_html_attribute_value(&parsed_tab_item.tab_value),
+            escape_html_attribute_value(&sanitize_identifier(&parsed_tab_item.tab_value)),
             escape_html_text_content(&parsed_tab_item.tab_label),
         );
     }
@@ -212,6 +849,7 @@ fn write_tabs_group_panels(
     parsed_tab_items: &[ParsedTabItem],
     active_tab_index: usize,
     sanitized_group_identifier: &str,
+    highlighting_enabled: bool,
 ) {
     for (tab_index, parsed_tab_item) in parsed_tab_items.iter().enumerate() {
         let is_active_tab = tab_index == active_tab_index;
@@ -225,23 +863,40 @@ fn write_tabs_group_panels(
             sanitized_group_identifier,
             sanitize_identifier(&parsed_tab_item.tab_value),
         );
-        let encoded_code_content =
-            escape_html_text_content(&parsed_tab_item.code_content).replace('\n', "&#10;");
-
         let _ = writeln!(
             rendered_tabs_html,
             r#"<section class="language-tabs-panel{}" role="tabpanel" id="{}" aria-labelledby="{}" data-language-tabs-value="{}">"#,
             if is_active_tab { " is-active" } else { "" },
             escape_html_attribute_value(&tab_panel_identifier),
             escape_html_attribute_value(&tab_button_identifier),
-            escape_html_attribute_value(&parsed_tab_item.tab_value),
-        );
-        let _ = writeln!(
-            rendered_tabs_html,
-            r#"<pre><code class="language-{}">{}</code></pre>"#,
-            escape_html_attribute_value(&parsed_tab_item.code_language),
-            encoded_code_content,
+            escape_html_attribute_value(&sanitize_identifier(&parsed_tab_item.tab_value)),
         );
+        if let Some(single_code_block) = &parsed_tab_item.single_code_block {
+            if let Some(code_title) = &single_code_block.title {
+                let _ = writeln!(
+                    rendered_tabs_html,
+                    r#"<div class="language-tabs-code-title">{}</div>"#,
+                    escape_html_text_content(code_title),
+                );
+            }
+            let _ = writeln!(
+                rendered_tabs_html,
+                r#"<pre{}><code class="language-{}">"#,
+                if single_code_block.show_line_numbers {
+                    " data-line-numbers"
+                } else {
+                    ""
+                },
+                escape_html_attribute_value(&single_code_block.code_language),
+            );
+            rendered_tabs_html.push_str(&render_single_code_block_lines_html(
+                single_code_block,
+                highlighting_enabled,
+            ));
+            rendered_tabs_html.push_str("</code></pre>\n");
+        } else {
+            rendered_tabs_html.push_str(&parsed_tab_item.rendered_inner_html);
+        }
         rendered_tabs_html.push_str("</section>\n");
     }
 }
@@ -251,25 +906,6 @@ fn write_tabs_group_end(rendered_tabs_html: &mut String) {
     rendered_tabs_html.push_str("</div>\n");
 }
 
-fn extract_first_fenced_code_block(tab_item_content: &str) -> Option<(String, String)> {
-    let fenced_code_block_regex =
-        Regex::new(r"(?s)```(?P<language>[^\r\n`]*)\r?\n(?P<code>.*?)\r?\n```").ok()?;
-    let capture = fenced_code_block_regex.captures(tab_item_content)?;
-
-    let raw_code_language = capture.name("language").map_or_else(
-        || "text".to_string(),
-        |value| value.as_str().trim().to_string(),
-    );
-    let normalized_code_language = if raw_code_language.is_empty() {
-        "text".to_string()
-    } else {
-        raw_code_language
-    };
-
-    let code_content = capture.name("code")?.as_str().to_string();
-    Some((normalized_code_language, code_content))
-}
-
 fn parse_attribute_value(attribute_source: &str, attribute_name: &str) -> Option<String> {
     let attribute_regex_pattern = format!(r#"{attribute_name}\s*=\s*"([^"]+)""#);
     let attribute_regex = Regex::new(&attribute_regex_pattern).ok()?;
@@ -312,6 +948,229 @@ fn escape_html_attribute_value(attribute_value: &str) -> String {
         .replace('\'', "&#39;")
 }
 
+struct LanguageLexerConfig {
+    line_comment_prefixes: &'static [&'static str],
+    block_comment_delimiters: Option<(&'static str, &'static str)>,
+    string_delimiters: &'static [char],
+    keywords: &'static [&'static str],
+}
+
+fn language_lexer_config(code_language: &str) -> Option<&'static LanguageLexerConfig> {
+    const RUST_LEXER_CONFIG: LanguageLexerConfig = LanguageLexerConfig {
+        line_comment_prefixes: &["//"],
+        block_comment_delimiters: Some(("/*", "*/")),
+        string_delimiters: &['"'],
+        keywords: &[
+            "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+            "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod",
+            "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super",
+            "trait", "true", "type", "union", "unsafe", "use", "where", "while",
+        ],
+    };
+    const TOML_LEXER_CONFIG: LanguageLexerConfig = LanguageLexerConfig {
+        line_comment_prefixes: &["#"],
+        block_comment_delimiters: None,
+        string_delimiters: &['"', '\''],
+        keywords: &["true", "false"],
+    };
+    const JSON_LEXER_CONFIG: LanguageLexerConfig = LanguageLexerConfig {
+        line_comment_prefixes: &[],
+        block_comment_delimiters: None,
+        string_delimiters: &['"'],
+        keywords: &["true", "false", "null"],
+    };
+    const SHELL_LEXER_CONFIG: LanguageLexerConfig = LanguageLexerConfig {
+        line_comment_prefixes: &["#"],
+        block_comment_delimiters: None,
+        string_delimiters: &['"', '\''],
+        keywords: &[
+            "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if",
+            "in", "local", "return", "then", "while",
+        ],
+    };
+
+    match code_language {
+        "rust" | "rs" => Some(&RUST_LEXER_CONFIG),
+        "toml" => Some(&TOML_LEXER_CONFIG),
+        "json" => Some(&JSON_LEXER_CONFIG),
+        "bash" | "sh" | "shell" | "console" => Some(&SHELL_LEXER_CONFIG),
+        _ => None,
+    }
+}
+
+/// Tokenizes `code_content` for `code_language` into classified `<span>` runs
+/// (mirroring rustdoc's `html/highlight.rs`), or `None` for a language that
+/// isn't in the table, in which case the caller falls back to plain text.
+/// Tokenizes `code_content` in a single pass and returns one HTML fragment per
+/// physical source line. A token that spans multiple lines (a `/* */` block
+/// comment, a `\`-continued string) re-opens its `<span class="...">` at the start
+/// of each line it crosses via `HighlightedLineBuilder::push_span`, so every line
+/// keeps the token's class instead of only the line containing its start.
+fn highlight_code_to_html_lines(code_language: &str, code_content: &str) -> Option<Vec<String>> {
+    let lexer_config = language_lexer_config(code_language)?;
+    let code_chars: Vec<char> = code_content.chars().collect();
+    let mut lines = HighlightedLineBuilder::default();
+    let mut cursor_index = 0usize;
+
+    while cursor_index < code_chars.len() {
+        if let Some((block_comment_start, block_comment_end)) =
+            lexer_config.block_comment_delimiters
+            && starts_with_at(&code_chars, cursor_index, block_comment_start)
+        {
+            let search_start_index = cursor_index + block_comment_start.chars().count();
+            let comment_end_index =
+                find_substring_from(&code_chars, search_start_index, block_comment_end)
+                    .map(|match_index| match_index + block_comment_end.chars().count())
+                    .unwrap_or(code_chars.len());
+            lines.push_span("comment", &code_chars[cursor_index..comment_end_index]);
+            cursor_index = comment_end_index;
+            continue;
+        }
+
+        if lexer_config
+            .line_comment_prefixes
+            .iter()
+            .any(|prefix| starts_with_at(&code_chars, cursor_index, prefix))
+        {
+            let line_end_index = code_chars[cursor_index..]
+                .iter()
+                .position(|&character| character == '\n')
+                .map_or(code_chars.len(), |relative_index| {
+                    cursor_index + relative_index
+                });
+            lines.push_span("comment", &code_chars[cursor_index..line_end_index]);
+            cursor_index = line_end_index;
+            continue;
+        }
+
+        let current_character = code_chars[cursor_index];
+
+        if lexer_config.string_delimiters.contains(&current_character) {
+            let string_end_index = find_closing_quote(&code_chars, cursor_index, current_character);
+            lines.push_span("string", &code_chars[cursor_index..string_end_index]);
+            cursor_index = string_end_index;
+            continue;
+        }
+
+        if current_character.is_ascii_digit() {
+            let number_end_index = consume_while(&code_chars, cursor_index, |character| {
+                character.is_ascii_alphanumeric() || character == '.' || character == '_'
+            });
+            lines.push_span("num", &code_chars[cursor_index..number_end_index]);
+            cursor_index = number_end_index;
+            continue;
+        }
+
+        if current_character.is_ascii_alphabetic() || current_character == '_' {
+            let identifier_end_index = consume_while(&code_chars, cursor_index, |character| {
+                character.is_ascii_alphanumeric() || character == '_'
+            });
+            let identifier_text: String =
+                code_chars[cursor_index..identifier_end_index].iter().collect();
+            let span_class = if lexer_config.keywords.contains(&identifier_text.as_str()) {
+                "kw"
+            } else {
+                "ident"
+            };
+            lines.push_span(span_class, &code_chars[cursor_index..identifier_end_index]);
+            cursor_index = identifier_end_index;
+            continue;
+        }
+
+        lines.push_plain_char(current_character);
+        cursor_index += 1;
+    }
+
+    Some(lines.finish())
+}
+
+fn starts_with_at(code_chars: &[char], start_index: usize, pattern: &str) -> bool {
+    let pattern_chars: Vec<char> = pattern.chars().collect();
+    start_index + pattern_chars.len() <= code_chars.len()
+        && code_chars[start_index..start_index + pattern_chars.len()] == pattern_chars[..]
+}
+
+fn find_substring_from(code_chars: &[char], start_index: usize, pattern: &str) -> Option<usize> {
+    let pattern_chars: Vec<char> = pattern.chars().collect();
+    if pattern_chars.is_empty() || start_index > code_chars.len() {
+        return None;
+    }
+    (start_index..=code_chars.len().saturating_sub(pattern_chars.len()))
+        .find(|&index| code_chars[index..index + pattern_chars.len()] == pattern_chars[..])
+}
+
+fn find_closing_quote(code_chars: &[char], quote_start_index: usize, quote_character: char) -> usize {
+    let mut cursor_index = quote_start_index + 1;
+    while cursor_index < code_chars.len() {
+        match code_chars[cursor_index] {
+            '\\' => cursor_index += 2,
+            character if character == quote_character => return cursor_index + 1,
+            _ => cursor_index += 1,
+        }
+    }
+    code_chars.len()
+}
+
+fn consume_while(code_chars: &[char], start_index: usize, predicate: impl Fn(char) -> bool) -> usize {
+    let mut cursor_index = start_index;
+    while cursor_index < code_chars.len() && predicate(code_chars[cursor_index]) {
+        cursor_index += 1;
+    }
+    cursor_index
+}
+
+/// Accumulates highlighted HTML one physical source line at a time.
+#[derive(Default)]
+struct HighlightedLineBuilder {
+    completed_lines: Vec<String>,
+    current_line: String,
+}
+
+impl HighlightedLineBuilder {
+    fn end_line(&mut self) {
+        self.completed_lines.push(std::mem::take(&mut self.current_line));
+    }
+
+    fn push_plain_char(&mut self, character: char) {
+        if character == '\n' {
+            self.end_line();
+        } else {
+            push_escaped_character(&mut self.current_line, character);
+        }
+    }
+
+    /// Pushes a classified span's characters, closing and re-opening the span at
+    /// every line boundary it crosses so a multi-line token keeps its class on
+    /// every physical line instead of only the line containing its first character.
+    fn push_span(&mut self, span_class: &str, span_chars: &[char]) {
+        let _ = write!(self.current_line, r#"<span class="{span_class}">"#);
+        for &character in span_chars {
+            if character == '\n' {
+                self.current_line.push_str("</span>");
+                self.end_line();
+                let _ = write!(self.current_line, r#"<span class="{span_class}">"#);
+            } else {
+                push_escaped_character(&mut self.current_line, character);
+            }
+        }
+        self.current_line.push_str("</span>");
+    }
+
+    fn finish(mut self) -> Vec<String> {
+        self.completed_lines.push(self.current_line);
+        self.completed_lines
+    }
+}
+
+fn push_escaped_character(highlighted_html: &mut String, character: char) {
+    match character {
+        '&' => highlighted_html.push_str("&amp;"),
+        '<' => highlighted_html.push_str("&lt;"),
+        '>' => highlighted_html.push_str("&gt;"),
+        other => highlighted_html.push(other),
+    }
+}
+
 fn main() -> Result<()> {
     let language_tabs_preprocessor = LanguageTabsPreprocessor;
     let argument_list: Vec<String> = env::args().collect();
@@ -341,3 +1200,56 @@ fn main() -> Result<()> {
     serde_json::to_writer(io::stdout(), &processed_book)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The canonical Docusaurus authoring style never puts a blank line between a
+    /// `<Tabs>`/`<TabItem>` open tag and the one that follows it, or between a
+    /// `</TabItem>` close and a sibling tag — which means CommonMark's HTML-block
+    /// rule 7 merges each such pair into a single `HtmlBlock` node. Every adjacent
+    /// pairing that can occur is exercised here: `<Tabs>`+`<TabItem>`,
+    /// `</TabItem>`+`<TabItem>`, and `</TabItem>`+`</Tabs>`.
+    #[test]
+    fn transform_docusaurus_tabs_blocks_handles_adjacent_tag_lines_with_no_blank_line() {
+        let markdown = "\
+<Tabs groupId=\"lang\">
+<TabItem value=\"a\" label=\"A\">
+
+fn a() {}
+
+</TabItem>
+<TabItem value=\"b\" label=\"B\">
+
+fn b() {}
+
+</TabItem>
+</Tabs>
+";
+
+        let transformed = transform_docusaurus_tabs_blocks(markdown, false);
+
+        assert!(!transformed.contains("<Tabs"));
+        assert!(!transformed.contains("<TabItem"));
+        assert!(transformed.contains("fn a()"));
+        assert!(transformed.contains("fn b()"));
+    }
+
+    /// The lexer has no cross-line state, so a `/* */` block comment is classified
+    /// by scanning for its closer across the whole tokenization pass rather than
+    /// line by line; the second physical line must keep the `comment` class instead
+    /// of being re-tokenized as ordinary code (`let`/`x` turning into `kw`/`ident`).
+    #[test]
+    fn highlight_code_to_html_lines_keeps_comment_class_across_a_multiline_block_comment() {
+        let lines = highlight_code_to_html_lines("rust", "/* a multi\nline comment */\nlet x = 1;")
+            .expect("rust has a lexer config");
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], r#"<span class="comment">/* a multi</span>"#);
+        assert_eq!(lines[1], r#"<span class="comment">line comment */</span>"#);
+        assert!(!lines[1].contains(r#"class="kw""#));
+        assert!(!lines[1].contains(r#"class="ident""#));
+        assert!(lines[2].contains(r#"<span class="kw">let</span>"#));
+    }
+}